@@ -40,9 +40,12 @@
 
 use ::std::mem;
 use ::std::fmt;
+use ::std::char;
 use ::std::string;
 use ::std::ffi::CString;
 use ::std::marker::PhantomData;
+use ::std::cell::RefCell;
+use ::std::collections::HashMap;
 
 use super::native::*;
 use super::j_chars::JavaChars;
@@ -259,6 +262,10 @@ impl JavaVM {
 				ptr: &mut *env,
 				phantom: PhantomData,
 				detach: false,
+				method_ids: RefCell::new(HashMap::new()),
+				static_method_ids: RefCell::new(HashMap::new()),
+				field_ids: RefCell::new(HashMap::new()),
+				static_field_ids: RefCell::new(HashMap::new()),
 			}, Capability::new())),
 			JniError::JNI_EDETACHED => {
 				let mut attachArgs = JavaVMAttachArgsImpl{
@@ -272,6 +279,10 @@ impl JavaVM {
 						ptr: &mut *env,
 						phantom: PhantomData,
 						detach: true,
+						method_ids: RefCell::new(HashMap::new()),
+						static_method_ids: RefCell::new(HashMap::new()),
+						field_ids: RefCell::new(HashMap::new()),
+						static_field_ids: RefCell::new(HashMap::new()),
 					}, Capability::new())),
 					_ => Err(res)
 				}
@@ -311,6 +322,12 @@ pub struct JavaEnv<'a> {
 	ptr: *mut JNIEnvImpl,
 	phantom: PhantomData<&'a JavaVM>,
 	detach: bool,
+	// Keyed by (class pointer, method/field name, descriptor); avoids
+	// re-resolving a `jmethodID`/`jfieldID` on every call.
+	method_ids: RefCell<HashMap<(usize, string::String, string::String), jmethodID>>,
+	static_method_ids: RefCell<HashMap<(usize, string::String, string::String), jmethodID>>,
+	field_ids: RefCell<HashMap<(usize, string::String, string::String), jfieldID>>,
+	static_field_ids: RefCell<HashMap<(usize, string::String, string::String), jfieldID>>,
 }
 
 // impl<'a> Clone for JavaEnv<'a> {
@@ -449,6 +466,38 @@ impl<'a> JavaEnv<'a> {
 		}
 	}
 
+	/// Runs `f`, turning an unwinding Rust panic into a pending Java
+	/// `RuntimeException` instead of letting it unwind across the JNI
+	/// boundary, which is undefined behavior (see the module docs on error
+	/// handling). Wrap the whole body of a `#[no_mangle] extern "C"` JNI
+	/// entry point in this so no panic ever escapes into the JVM -- only
+	/// the Rust backtrace is lost.
+	///
+	/// Returns `default` if `f` returns a pending Java exception or panics.
+	pub fn catch_panic<R, F>(&self, cap: Capability, default: R, f: F) -> R
+		where F: FnOnce(Capability) -> JniResult<R> + ::std::panic::UnwindSafe {
+		match ::std::panic::catch_unwind(move || f(cap)) {
+			Ok(Ok((value, _cap))) => value,
+			Ok(Err(_exn)) => default,
+			Err(payload) => {
+				let msg = if let Some(s) = payload.downcast_ref::<&str>() {
+					s.to_string()
+				} else if let Some(s) = payload.downcast_ref::<string::String>() {
+					s.clone()
+				} else {
+					"Rust panic in native method".to_string()
+				};
+				// Don't double-throw: only `ThrowNew` if nothing is pending already.
+				if let Ok(cap) = self.exception_check() {
+					if let Ok((class, cap)) = self.find_class("java/lang/RuntimeException", cap) {
+						let _ = self.throw_new(&class, &msg, cap);
+					}
+				}
+				default
+			},
+		}
+	}
+
 	pub fn push_local_frame(&self, capacity: isize, cap: Capability) -> Result<Capability, (JniError, Exception)> {
 		let (err, _) = unsafe {
 			(((**self.ptr).PushLocalFrame)(self.ptr, capacity as jint), cap)
@@ -467,15 +516,64 @@ impl<'a> JavaEnv<'a> {
 		};
 	}
 
-	pub fn pop_local_frame<T: JObject<'a>>(&'a self, result: &'a T, _cap: &Capability) -> T {
+	pub fn pop_local_frame<T: JObject<'a>>(&'a self, result: T, _cap: &Capability) -> T {
+		let obj = result.get_obj();
+		mem::forget(result);
 		let r = unsafe {
-			((**self.ptr).PopLocalFrame)(self.ptr, result.get_obj())
+			((**self.ptr).PopLocalFrame)(self.ptr, obj)
 		};
 		// documentation says, it never returns null
 		assert!(r != 0 as jobject);
 		unsafe { JObject::from_unsafe(self, r) }
 	}
 
+	/// Runs `f` inside a `PushLocalFrame`/`PopLocalFrame` pair, so every
+	/// local ref `f` creates is freed when it returns, without having to
+	/// balance the push/pop by hand. The frame is always popped with
+	/// `null`, so a `JObject` result would not survive it -- use
+	/// `with_local_frame_obj` to carry a `JObject` result out into the
+	/// parent frame.
+	pub fn with_local_frame<R, F>(&'a self, capacity: isize, cap: Capability, f: F) -> JniResult<R>
+		where F: FnOnce(Capability) -> JniResult<R> {
+		let cap = match self.push_local_frame(capacity, cap) {
+			Ok(cap) => cap,
+			Err((err, _exn)) => self.fatal_error(&format!("PushLocalFrame error: {:?}", err)),
+		};
+		let result = f(cap);
+		match result {
+			Ok((value, cap)) => {
+				self.pop_local_frame_null::<JavaObject>(&cap);
+				Ok((value, Capability::new()))
+			},
+			Err(exn) => {
+				self.pop_local_frame_null::<JavaObject>(&Capability::new());
+				let _ = exn;
+				Err(Exception::new())
+			},
+		}
+	}
+
+	/// Like `with_local_frame`, but carries the single `JObject` result
+	/// through `PopLocalFrame` so it survives into the parent frame.
+	pub fn with_local_frame_obj<T, F>(&'a self, capacity: isize, cap: Capability, f: F) -> JniResult<T>
+		where T: JObject<'a>, F: FnOnce(Capability) -> JniResult<T> {
+		let cap = match self.push_local_frame(capacity, cap) {
+			Ok(cap) => cap,
+			Err((err, _exn)) => self.fatal_error(&format!("PushLocalFrame error: {:?}", err)),
+		};
+		match f(cap) {
+			Ok((value, cap)) => {
+				let value = self.pop_local_frame(value, &cap);
+				Ok((value, Capability::new()))
+			},
+			Err(exn) => {
+				self.pop_local_frame_null::<T>(&Capability::new());
+				let _ = exn;
+				Err(Exception::new())
+			},
+		}
+	}
+
 	pub fn is_same_object<T1: JObject<'a>, T2: JObject<'a>>(&self, obj1: &T1, obj2: &T2, _cap: &Capability) -> bool {
 		unsafe {
 			((**self.ptr).IsSameObject)(self.ptr, obj1.get_obj(), obj2.get_obj()) == JNI_TRUE
@@ -589,6 +687,72 @@ impl<'a> Drop for JavaEnv<'a> {
 	}
 }
 
+/// The outcome of a `try_block`: either the block's result alongside proof
+/// there is no pending exception, or the still-pending `Exception` for
+/// `.catch` to match against.
+pub struct TryCatch<'a, T> {
+	env: &'a JavaEnv<'a>,
+	state: JniResult<T>,
+}
+
+impl<'a> JavaEnv<'a> {
+	/// Runs `f`, capturing either its result or the `Exception` it left
+	/// pending, so `.catch` can match specific Java exception classes
+	/// against it before falling back to `.result()`.
+	///
+	/// Always re-checks for a pending exception before running `f`, so a
+	/// stale exception can never be mistaken for one `f` raised.
+	pub fn try_block<T, F>(&'a self, cap: Capability, f: F) -> TryCatch<'a, T>
+		where F: FnOnce(Capability) -> JniResult<T> {
+		let _ = cap;
+		let state = match self.exception_check() {
+			Ok(cap) => f(cap),
+			Err(exn) => Err(exn),
+		};
+		TryCatch { env: self, state: state }
+	}
+}
+
+impl<'a, T> TryCatch<'a, T> {
+	/// If a pending exception is an instance of `class`, clears it and runs
+	/// `handler` with the thrown `JavaThrowable` and a fresh `Capability`.
+	/// Otherwise leaves the exception (or success value) untouched, so a
+	/// later `.catch` or the final `.result()` can observe it.
+	pub fn catch<F>(self, class: &JavaClass, handler: F) -> TryCatch<'a, T>
+		where F: FnOnce(JavaThrowable<'a>, Capability) -> JniResult<T> {
+		let env = self.env;
+		match self.state {
+			Err(exn) => {
+				match env.exception_occured() {
+					Err((thrown, _)) => {
+						// IsInstanceOf isn't safe to call with an exception
+						// still pending -- clear it first to get a real
+						// Capability, then re-throw if `thrown` turns out
+						// not to match, so the pending state is restored
+						// for whatever catches or observes it next.
+						let cap = env.exception_clear(Exception::new());
+						if thrown.is_instance_of(class, &cap) {
+							TryCatch { env: env, state: handler(thrown, cap) }
+						} else {
+							match env.throw(&thrown, cap) {
+								Ok(_) => TryCatch { env: env, state: Err(exn) },
+								Err(err) => env.fatal_error(&format!("Throw error: {:?}", err)),
+							}
+						}
+					},
+					Ok(_) => TryCatch { env: env, state: Err(exn) },
+				}
+			},
+			ok => TryCatch { env: env, state: ok },
+		}
+	}
+
+	/// Collapses the chain back into a plain `JniResult`.
+	pub fn result(self) -> JniResult<T> {
+		self.state
+	}
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum RefType {
 	Local,
@@ -697,9 +861,63 @@ pub trait JObject<'a>: Drop {
 			((**val.ptr).IsSameObject)(val.ptr, self.get_obj(), 0 as jobject) == JNI_TRUE
 		}
 	}
+
+	/// Unwraps the reference as a raw `jobject`, without running `Drop`.
+	/// Use this instead of `get_obj()` whenever the pointer is being
+	/// handed off to a caller that will own it (e.g. returning a
+	/// `jobject` from `IntoJava::into_java`) -- `get_obj()` only copies
+	/// the pointer, so if the wrapper is then dropped in the usual way,
+	/// its `Drop` impl deletes the very reference just handed out.
+	fn into_raw(self) -> jobject where Self: Sized {
+		let ptr = self.get_obj();
+		mem::forget(self);
+		ptr
+	}
 }
 // pub trait JArray<'a, T: 'a + JObject<'a>>: JObject<'a> {}
 
+/// Wraps a local reference so it reads as an explicit, scoped hand-off: the
+/// wrapped local ref is deleted as soon as this value is dropped. Useful in
+/// a long-running native loop to bound local-ref growth without waiting for
+/// the whole `JavaEnv` to go out of scope.
+///
+/// `inner` is held in a `ManuallyDrop` so `T`'s own `Drop` impl (which would
+/// otherwise delete the same local ref a second time, right after this
+/// type's own `Drop` already did) never runs.
+pub struct AutoLocal<'a, T: 'a + JObject<'a>> {
+	inner: mem::ManuallyDrop<T>,
+	phantom: PhantomData<&'a ()>,
+}
+
+impl<'a, T: 'a + JObject<'a>> AutoLocal<'a, T> {
+	pub fn new(inner: T) -> AutoLocal<'a, T> {
+		assert!(inner.ref_type() == RefType::Local);
+		AutoLocal { inner: mem::ManuallyDrop::new(inner), phantom: PhantomData }
+	}
+
+	/// Unwraps the local reference, taking it out from under the guard
+	/// without deleting it.
+	pub fn into_inner(mut self) -> T {
+		let inner = unsafe { mem::ManuallyDrop::take(&mut self.inner) };
+		mem::forget(self);
+		inner
+	}
+}
+
+impl<'a, T: 'a + JObject<'a>> Drop for AutoLocal<'a, T> {
+	fn drop(&mut self) {
+		let env = self.inner.get_env();
+		env.delete_local_ref(&*self.inner);
+	}
+}
+
+impl<'a, T: 'a + JObject<'a>> ::std::ops::Deref for AutoLocal<'a, T> {
+	type Target = T;
+	fn deref(&self) -> &T {
+		&self.inner
+	}
+}
+
 macro_rules! impl_jobject(
 	($cls:ident, $native:ident) => (
 		impl<'a> Drop for $cls<'a> {
@@ -818,6 +1036,10 @@ pub struct JavaString<'a> {
 impl_jobject!(JavaString, jstring);
 
 impl<'a> JavaString<'a> {
+	/// Constructs a new Java string from `val` via `NewStringUTF`, i.e.
+	/// JNI's *modified* UTF-8. This corrupts `val`s with embedded NULs or
+	/// characters outside the BMP -- use `new_utf16` to move those across
+	/// losslessly.
 	pub fn new<'b>(env: &'b JavaEnv<'b>, val: &str, cap: Capability) -> JniResult<JavaString<'b>> {
 		let jval = JavaChars::new(val);
 		let (r, _) = unsafe {
@@ -831,6 +1053,24 @@ impl<'a> JavaString<'a> {
 		}
 	}
 
+	/// Constructs a new Java string from `val` via `NewString` over UTF-16
+	/// code units -- the only lossless way to move an arbitrary `&str`
+	/// (one that may contain embedded NULs or non-BMP characters) into
+	/// Java. `new` goes through modified UTF-8 instead and will corrupt
+	/// those.
+	pub fn new_utf16<'b>(env: &'b JavaEnv<'b>, val: &str, cap: Capability) -> JniResult<JavaString<'b>> {
+		let units: Vec<jchar> = val.encode_utf16().collect();
+		let (r, _) = unsafe {
+			(((**env.ptr).NewString)(env.ptr, units.as_ptr(), units.len() as jsize), cap)
+		};
+		// here `cap` is taken, we can't call any Jni methods
+		if r == 0 as jstring {
+			Err(Exception::new())
+		} else {
+			Ok(( unsafe { JObject::from_unsafe(env, r) }, Capability::new()))
+		}
+	}
+
 	pub fn len(&self, _cap: &Capability) -> usize {
 		unsafe {
 			((**self.get_env().ptr).GetStringLength)(self.get_env().ptr, self.ptr) as usize
@@ -843,11 +1083,44 @@ impl<'a> JavaString<'a> {
 		}
 	}
 
+	/// Decodes the string via JNI's *modified* UTF-8 (`GetStringUTFChars`).
+	/// Modified UTF-8 encodes embedded NULs and characters outside the BMP
+	/// differently from standard UTF-8, so a string holding either is not
+	/// decoded faithfully here. Use `to_string_utf16` for a lossless decode
+	/// of arbitrary string content.
 	pub fn to_str(&self) -> Option<string::String> {
 		let (chars, _) = self.chars();
 		chars.to_str()
 	}
 
+	/// Whether this string's modified-UTF-8 bytes are also valid standard
+	/// UTF-8, i.e. whether `to_str` is guaranteed to decode it losslessly.
+	/// A `false` here doesn't necessarily mean the string holds non-BMP
+	/// characters or embedded NULs -- only that `to_str` can't be trusted,
+	/// and `to_string_utf16` should be used instead.
+	pub fn is_valid_utf8(&self) -> bool {
+		let (chars, _) = self.chars();
+		chars.is_valid_utf8()
+	}
+
+	/// Decodes the string as real UTF-16 via `GetStringChars`, the only
+	/// lossless way to move arbitrary string content (embedded NULs,
+	/// characters outside the BMP) across JNI. See `to_str` for the
+	/// modified-UTF-8 alternative.
+	pub fn to_string_utf16(&self) -> Option<string::String> {
+		let env = self.get_env();
+		let len = self.len(&Capability::new());
+		let ptr = unsafe {
+			((**env.ptr).GetStringChars)(env.ptr, self.ptr, 0 as *mut jboolean)
+		};
+		let units = unsafe { ::std::slice::from_raw_parts(ptr, len) };
+		let result: Result<string::String, _> = char::decode_utf16(units.iter().cloned()).collect();
+		unsafe {
+			((**env.ptr).ReleaseStringChars)(env.ptr, self.ptr, ptr);
+		}
+		result.ok()
+	}
+
 	fn chars(&self) -> (JavaStringChars, bool) {
 		let mut isCopy: jboolean = 0;
 		let result = JavaStringChars{
@@ -903,28 +1176,74 @@ impl<'a> JavaStringChars<'a> {
 					)
 		}.to_string()
 	}
+
+	/// Whether the raw bytes behind this handle are valid standard UTF-8,
+	/// independent of whether `to_str`'s modified-UTF-8 decode happens to
+	/// succeed.
+	fn is_valid_utf8(&self) -> bool {
+		let bytes = unsafe { ::std::ffi::CStr::from_ptr(self.chars).to_bytes() };
+		::std::str::from_utf8(bytes).is_ok()
+	}
+}
+
+/// A JNI primitive array element type. Maps to the matching family of
+/// `New*Array`/`Get*ArrayRegion`/`Set*ArrayRegion`/`Get*ArrayElements`/
+/// `Release*ArrayElements` functions, so `JavaArray<T>`/`AutoArray<T>` need
+/// only be written once and instantiated per primitive type.
+pub trait JavaPrimitive: Copy {
+	unsafe fn new_array(env: *mut JNIEnvImpl, len: jsize) -> jarray;
+	unsafe fn get_region(env: *mut JNIEnvImpl, array: jarray, start: jsize, len: jsize, buf: *mut Self);
+	unsafe fn set_region(env: *mut JNIEnvImpl, array: jarray, start: jsize, len: jsize, buf: *const Self);
+	unsafe fn get_elements(env: *mut JNIEnvImpl, array: jarray, is_copy: *mut jboolean) -> *mut Self;
+	unsafe fn release_elements(env: *mut JNIEnvImpl, array: jarray, elems: *mut Self, mode: jint);
 }
 
-// For future
-trait JavaPrimitive {}
+macro_rules! impl_java_primitive(
+	($rust_ty:ty, $new:ident, $get_region:ident, $set_region:ident, $get_elems:ident, $release_elems:ident) => (
+		impl JavaPrimitive for $rust_ty {
+			unsafe fn new_array(env: *mut JNIEnvImpl, len: jsize) -> jarray {
+				((**env).$new)(env, len) as jarray
+			}
+
+			unsafe fn get_region(env: *mut JNIEnvImpl, array: jarray, start: jsize, len: jsize, buf: *mut $rust_ty) {
+				((**env).$get_region)(env, array, start, len, buf)
+			}
+
+			unsafe fn set_region(env: *mut JNIEnvImpl, array: jarray, start: jsize, len: jsize, buf: *const $rust_ty) {
+				((**env).$set_region)(env, array, start, len, buf)
+			}
 
-impl JavaPrimitive for jboolean {}
-impl JavaPrimitive for jbyte {}
-impl JavaPrimitive for jchar {}
-impl JavaPrimitive for jshort {}
-impl JavaPrimitive for jint {}
-impl JavaPrimitive for jlong {}
-impl JavaPrimitive for jfloat {}
-impl JavaPrimitive for jdouble {}
+			unsafe fn get_elements(env: *mut JNIEnvImpl, array: jarray, is_copy: *mut jboolean) -> *mut $rust_ty {
+				((**env).$get_elems)(env, array, is_copy)
+			}
 
-pub struct JavaArray<'a, T: 'a + JObject<'a>> {
+			unsafe fn release_elements(env: *mut JNIEnvImpl, array: jarray, elems: *mut $rust_ty, mode: jint) {
+				((**env).$release_elems)(env, array, elems, mode)
+			}
+		}
+	);
+);
+
+impl_java_primitive!(jboolean, NewBooleanArray, GetBooleanArrayRegion, SetBooleanArrayRegion, GetBooleanArrayElements, ReleaseBooleanArrayElements);
+impl_java_primitive!(jbyte, NewByteArray, GetByteArrayRegion, SetByteArrayRegion, GetByteArrayElements, ReleaseByteArrayElements);
+impl_java_primitive!(jchar, NewCharArray, GetCharArrayRegion, SetCharArrayRegion, GetCharArrayElements, ReleaseCharArrayElements);
+impl_java_primitive!(jshort, NewShortArray, GetShortArrayRegion, SetShortArrayRegion, GetShortArrayElements, ReleaseShortArrayElements);
+impl_java_primitive!(jint, NewIntArray, GetIntArrayRegion, SetIntArrayRegion, GetIntArrayElements, ReleaseIntArrayElements);
+impl_java_primitive!(jlong, NewLongArray, GetLongArrayRegion, SetLongArrayRegion, GetLongArrayElements, ReleaseLongArrayElements);
+impl_java_primitive!(jfloat, NewFloatArray, GetFloatArrayRegion, SetFloatArrayRegion, GetFloatArrayElements, ReleaseFloatArrayElements);
+impl_java_primitive!(jdouble, NewDoubleArray, GetDoubleArrayRegion, SetDoubleArrayRegion, GetDoubleArrayElements, ReleaseDoubleArrayElements);
+
+/// A Java array. `T` is either a `JavaPrimitive` (a primitive element
+/// array) or a `JObject` (an object element array, created via
+/// `new_object_array`).
+pub struct JavaArray<'a, T: 'a> {
 	env: &'a JavaEnv<'a>,
 	ptr: jarray,
 	rtype: RefType,
 	phantom: PhantomData<T>,
 }
 
-impl<'a, T: 'a + JObject<'a>> Drop for JavaArray<'a, T> {
+impl<'a, T: 'a> Drop for JavaArray<'a, T> {
 	fn drop(&mut self) {
 		let env = self.get_env();
 		match self.ref_type() {
@@ -935,15 +1254,15 @@ impl<'a, T: 'a + JObject<'a>> Drop for JavaArray<'a, T> {
 	}
 }
 
-impl<'a, T: 'a + JObject<'a>, R: 'a + JObject<'a>> PartialEq<R> for JavaArray<'a, T> {
+impl<'a, T: 'a, R: 'a + JObject<'a>> PartialEq<R> for JavaArray<'a, T> {
 	fn eq(&self, other: &R) -> bool {
 		self.is_same(other)
 	}
 }
 
-impl<'a, T: 'a + JObject<'a>> Eq for JavaArray<'a, T> {}
+impl<'a, T: 'a> Eq for JavaArray<'a, T> {}
 
-impl<'a, T: 'a + JObject<'a>> JObject<'a> for JavaArray<'a, T> {
+impl<'a, T: 'a> JObject<'a> for JavaArray<'a, T> {
 	fn get_env(&self) -> &'a JavaEnv<'a> {
 		self.env
 	}
@@ -966,6 +1285,1242 @@ impl<'a, T: 'a + JObject<'a>> JObject<'a> for JavaArray<'a, T> {
 	}
 }
 
+impl<'a, T: 'a> JavaArray<'a, T> {
+	/// The array's length, via `GetArrayLength`.
+	pub fn array_length(&self, _cap: &Capability) -> jsize {
+		unsafe {
+			((**self.env.ptr).GetArrayLength)(self.env.ptr, self.ptr)
+		}
+	}
+}
+
+impl<'a, T: 'a + JavaPrimitive> JavaArray<'a, T> {
+	/// Copies `buf.len()` elements starting at `start` out of the array.
+	pub fn get_region(&self, start: jsize, buf: &mut [T], _cap: &Capability) {
+		unsafe {
+			T::get_region(self.env.ptr, self.ptr, start, buf.len() as jsize, buf.as_mut_ptr())
+		}
+	}
+
+	/// Copies `buf` into the array starting at `start`.
+	pub fn set_region(&self, start: jsize, buf: &[T], _cap: &Capability) {
+		unsafe {
+			T::set_region(self.env.ptr, self.ptr, start, buf.len() as jsize, buf.as_ptr())
+		}
+	}
+
+	/// Pins the array for bulk access via `Get*ArrayElements`. The
+	/// returned guard releases the pin (per `mode`) when dropped.
+	pub fn elements(&'a self, mode: ReleaseMode, _cap: &Capability) -> AutoArray<'a, T> {
+		let mut is_copy: jboolean = 0;
+		let ptr = unsafe { T::get_elements(self.env.ptr, self.ptr, &mut is_copy) };
+		AutoArray {
+			array: self,
+			ptr: ptr,
+			is_copy: is_copy != 0,
+			mode: mode,
+		}
+	}
+
+	/// Pins the array via `GetPrimitiveArrayCritical`. No other JNI call is
+	/// legal while the returned guard is alive -- it borrows `cap` for its
+	/// whole lifetime so the borrow checker enforces that.
+	pub fn critical(&'a self, mode: ReleaseMode, cap: &'a Capability) -> AutoArrayCritical<'a, T> {
+		// GetArrayLength must run before GetPrimitiveArrayCritical pins the
+		// array -- no JNI call, including this one, is legal once the array
+		// is pinned and before AutoArrayCritical's guard exists to enforce that.
+		let len = self.array_length(cap);
+		let mut is_copy: jboolean = 0;
+		let ptr = unsafe {
+			((**self.env.ptr).GetPrimitiveArrayCritical)(self.env.ptr, self.ptr, &mut is_copy) as *mut T
+		};
+		AutoArrayCritical {
+			array: self,
+			ptr: ptr,
+			len: len,
+			is_copy: is_copy != 0,
+			mode: mode,
+			_cap: cap,
+		}
+	}
+}
+
+/// Given when an `AutoArray`/`AutoArrayCritical` is created and applied
+/// when it is dropped, to control whether (and how) pinned elements are
+/// copied back to the Java array.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReleaseMode {
+	/// Copy the (possibly modified) elements back and free the buffer.
+	CopyBack,
+	/// Copy the elements back but do not free the buffer (`JNI_COMMIT`).
+	CommitNoCopyBack,
+	/// Free the buffer without copying back any modifications (`JNI_ABORT`).
+	Abort,
+}
+
+impl ReleaseMode {
+	fn as_jint(&self) -> jint {
+		match *self {
+			ReleaseMode::CopyBack => 0,
+			ReleaseMode::CommitNoCopyBack => 1, // JNI_COMMIT
+			ReleaseMode::Abort => 2, // JNI_ABORT
+		}
+	}
+}
+
+/// RAII handle for a `Get*ArrayElements`/`Release*ArrayElements` pin,
+/// releasing it (per its `ReleaseMode`) on drop.
+pub struct AutoArray<'a, T: 'a + JavaPrimitive> {
+	array: &'a JavaArray<'a, T>,
+	ptr: *mut T,
+	is_copy: bool,
+	mode: ReleaseMode,
+}
+
+impl<'a, T: 'a + JavaPrimitive> AutoArray<'a, T> {
+	/// Whether the JVM gave back a copy of the elements rather than a
+	/// direct pointer into the array's storage.
+	pub fn is_copy(&self) -> bool {
+		self.is_copy
+	}
+
+	pub fn as_slice(&self) -> &[T] {
+		let len = self.array.array_length(&Capability::new());
+		unsafe { ::std::slice::from_raw_parts(self.ptr, len as usize) }
+	}
+
+	pub fn as_mut_slice(&mut self) -> &mut [T] {
+		let len = self.array.array_length(&Capability::new());
+		unsafe { ::std::slice::from_raw_parts_mut(self.ptr, len as usize) }
+	}
+}
+
+impl<'a, T: 'a + JavaPrimitive> Drop for AutoArray<'a, T> {
+	fn drop(&mut self) {
+		unsafe {
+			T::release_elements(self.array.env.ptr, self.array.ptr, self.ptr, self.mode.as_jint());
+		}
+	}
+}
+
+/// RAII handle for a `GetPrimitiveArrayCritical`/
+/// `ReleasePrimitiveArrayCritical` pin. No other JNI call may be made for
+/// as long as this guard is alive.
+pub struct AutoArrayCritical<'a, T: 'a + JavaPrimitive> {
+	array: &'a JavaArray<'a, T>,
+	ptr: *mut T,
+	len: jsize,
+	is_copy: bool,
+	mode: ReleaseMode,
+	_cap: &'a Capability,
+}
+
+impl<'a, T: 'a + JavaPrimitive> AutoArrayCritical<'a, T> {
+	pub fn is_copy(&self) -> bool {
+		self.is_copy
+	}
+
+	pub fn as_slice(&self) -> &[T] {
+		unsafe { ::std::slice::from_raw_parts(self.ptr, self.len as usize) }
+	}
+
+	pub fn as_mut_slice(&mut self) -> &mut [T] {
+		unsafe { ::std::slice::from_raw_parts_mut(self.ptr, self.len as usize) }
+	}
+}
+
+impl<'a, T: 'a + JavaPrimitive> Drop for AutoArrayCritical<'a, T> {
+	fn drop(&mut self) {
+		unsafe {
+			((**self.array.env.ptr).ReleasePrimitiveArrayCritical)(
+				self.array.env.ptr, self.array.ptr, self.ptr as *mut ::libc::c_void, self.mode.as_jint());
+		}
+	}
+}
+
+/// A zero-copy pin of a primitive array's backing storage: either an
+/// `AutoArray` (`Get*ArrayElements`) or, when `critical` is requested, the
+/// `AutoArrayCritical` (`GetPrimitiveArrayCritical`) variant. Prefer
+/// critical for short, hot bulk accesses -- many JVMs avoid a copy there --
+/// but no other JNI call is legal for as long as it's held.
+pub enum AutoPrimitiveArray<'a, T: 'a + JavaPrimitive> {
+	Normal(AutoArray<'a, T>),
+	Critical(AutoArrayCritical<'a, T>),
+}
+
+impl<'a, T: 'a + JavaPrimitive> JavaArray<'a, T> {
+	/// Pins the array for bulk access, dispatching to `elements` or
+	/// `critical` depending on `critical`.
+	pub fn pin(&'a self, critical: bool, mode: ReleaseMode, cap: &'a Capability) -> AutoPrimitiveArray<'a, T> {
+		if critical {
+			AutoPrimitiveArray::Critical(self.critical(mode, cap))
+		} else {
+			AutoPrimitiveArray::Normal(self.elements(mode, cap))
+		}
+	}
+}
+
+impl<'a, T: 'a + JavaPrimitive> AutoPrimitiveArray<'a, T> {
+	pub fn is_copy(&self) -> bool {
+		match *self {
+			AutoPrimitiveArray::Normal(ref a) => a.is_copy(),
+			AutoPrimitiveArray::Critical(ref a) => a.is_copy(),
+		}
+	}
+
+	pub fn as_slice(&self) -> &[T] {
+		match *self {
+			AutoPrimitiveArray::Normal(ref a) => a.as_slice(),
+			AutoPrimitiveArray::Critical(ref a) => a.as_slice(),
+		}
+	}
+
+	pub fn as_mut_slice(&mut self) -> &mut [T] {
+		match *self {
+			AutoPrimitiveArray::Normal(ref mut a) => a.as_mut_slice(),
+			AutoPrimitiveArray::Critical(ref mut a) => a.as_mut_slice(),
+		}
+	}
+}
+
+impl<'a, T: 'a + JObject<'a>> JavaArray<'a, T> {
+	/// Reads the element at `index`, or `None` if it is `null`. Raises
+	/// `ArrayIndexOutOfBoundsException` if `index` is out of bounds.
+	pub fn get(&'a self, index: jsize, cap: Capability) -> JniResult<Option<T>> {
+		let (obj, _) = unsafe {
+			(((**self.env.ptr).GetObjectArrayElement)(self.env.ptr, self.ptr, index), cap)
+		};
+		// here `cap` is taken, we can't call any Jni methods
+		match self.env.exception_check() {
+			Ok(cap) => Ok((JObject::from(self.env, obj), cap)),
+			Err(exn) => Err(exn),
+		}
+	}
+
+	/// Writes `value` (or `null`, if `None`) at `index`. Raises
+	/// `ArrayIndexOutOfBoundsException` if `index` is out of bounds, or
+	/// `ArrayStoreException` if `value` isn't assignable to the array's
+	/// element type.
+	pub fn set(&self, index: jsize, value: Option<&T>, cap: Capability) -> JniResult<()> {
+		let obj = value.map(|v| v.get_obj()).unwrap_or(0 as jobject);
+		let (_, _) = unsafe {
+			(((**self.env.ptr).SetObjectArrayElement)(self.env.ptr, self.ptr, index, obj), cap)
+		};
+		// here `cap` is taken, we can't call any Jni methods
+		match self.env.exception_check() {
+			Ok(cap) => Ok(((), cap)),
+			Err(exn) => Err(exn),
+		}
+	}
+}
+
+macro_rules! impl_new_primitive_array(
+	($fn_name:ident, $rust_ty:ty) => (
+		impl<'a> JavaEnv<'a> {
+			/// Allocates a new Java primitive array of the given length.
+			pub fn $fn_name(&'a self, len: jsize, cap: Capability) -> JniResult<JavaArray<'a, $rust_ty>> {
+				let (arr, _) = unsafe {
+					(<$rust_ty as JavaPrimitive>::new_array(self.ptr, len), cap)
+				};
+				// here `cap` is taken, we can't call any Jni methods
+				if arr == 0 as jarray {
+					Err(Exception::new())
+				} else {
+					Ok((JavaArray { env: self, ptr: arr, rtype: RefType::Local, phantom: PhantomData }, Capability::new()))
+				}
+			}
+		}
+	);
+);
+
+impl_new_primitive_array!(new_boolean_array, jboolean);
+impl_new_primitive_array!(new_byte_array, jbyte);
+impl_new_primitive_array!(new_char_array, jchar);
+impl_new_primitive_array!(new_short_array, jshort);
+impl_new_primitive_array!(new_int_array, jint);
+impl_new_primitive_array!(new_long_array, jlong);
+impl_new_primitive_array!(new_float_array, jfloat);
+impl_new_primitive_array!(new_double_array, jdouble);
+
+impl<'a> JavaEnv<'a> {
+	/// Allocates a new Java object array of the given length and element
+	/// class, with every element initialized to `initial` (or `null`).
+	pub fn new_object_array<T: 'a + JObject<'a>>(&'a self, len: jsize, class: &JavaClass, initial: Option<&T>, cap: Capability) -> JniResult<JavaArray<'a, T>> {
+		let init = initial.map(|o| o.get_obj()).unwrap_or(0 as jobject);
+		let (arr, _) = unsafe {
+			(((**self.ptr).NewObjectArray)(self.ptr, len, class.ptr, init), cap)
+		};
+		// here `cap` is taken, we can't call any Jni methods
+		if arr == 0 as jarray {
+			Err(Exception::new())
+		} else {
+			Ok((JavaArray { env: self, ptr: arr, rtype: RefType::Local, phantom: PhantomData }, Capability::new()))
+		}
+	}
+}
+
+/// Parsing of JNI type descriptors, e.g. `"(ILjava/lang/String;)[I"`.
+pub mod signature {
+	use ::std::str::Chars;
+	use ::std::iter::Peekable;
+	use ::std::string;
+
+	/// A JNI primitive type.
+	#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+	pub enum Primitive {
+		Boolean,
+		Byte,
+		Char,
+		Short,
+		Int,
+		Long,
+		Float,
+		Double,
+		Void,
+	}
+
+	/// A single JNI type: a primitive, an object class (by internal name,
+	/// e.g. `"java/lang/String"`), or an array of either.
+	#[derive(Debug, Clone, PartialEq, Eq)]
+	pub enum JavaType {
+		Primitive(Primitive),
+		Object(string::String),
+		Array(Box<JavaType>),
+	}
+
+	impl JavaType {
+		/// Parses a single JNI field/type descriptor, e.g. `"I"` or
+		/// `"[Ljava/lang/String;"`. Returns `None` if malformed.
+		pub fn parse(descriptor: &str) -> Option<JavaType> {
+			let mut chars = descriptor.chars().peekable();
+			let typ = parse_type(&mut chars);
+			if chars.next().is_some() {
+				return None;
+			}
+			typ
+		}
+	}
+
+	fn parse_type<'b>(chars: &mut Peekable<Chars<'b>>) -> Option<JavaType> {
+		match chars.next() {
+			Some('Z') => Some(JavaType::Primitive(Primitive::Boolean)),
+			Some('B') => Some(JavaType::Primitive(Primitive::Byte)),
+			Some('C') => Some(JavaType::Primitive(Primitive::Char)),
+			Some('S') => Some(JavaType::Primitive(Primitive::Short)),
+			Some('I') => Some(JavaType::Primitive(Primitive::Int)),
+			Some('J') => Some(JavaType::Primitive(Primitive::Long)),
+			Some('F') => Some(JavaType::Primitive(Primitive::Float)),
+			Some('D') => Some(JavaType::Primitive(Primitive::Double)),
+			Some('V') => Some(JavaType::Primitive(Primitive::Void)),
+			Some('L') => {
+				let mut name = string::String::new();
+				loop {
+					match chars.next() {
+						Some(';') => break,
+						Some(c) => name.push(c),
+						None => return None,
+					}
+				}
+				Some(JavaType::Object(name))
+			},
+			Some('[') => parse_type(chars).map(|t| JavaType::Array(Box::new(t))),
+			_ => None,
+		}
+	}
+
+	/// The parsed form of a JNI method descriptor, e.g.
+	/// `"(ILjava/lang/String;)[I"`.
+	#[derive(Debug, Clone, PartialEq, Eq)]
+	pub struct TypeSignature {
+		pub args: Vec<JavaType>,
+		pub ret: JavaType,
+	}
+
+	impl TypeSignature {
+		/// Parses a JNI method descriptor. Returns `None` if malformed.
+		pub fn parse(descriptor: &str) -> Option<TypeSignature> {
+			let mut chars = descriptor.chars().peekable();
+			if chars.next() != Some('(') {
+				return None;
+			}
+
+			let mut args = vec![];
+			loop {
+				match chars.peek() {
+					Some(&')') => { chars.next(); break; },
+					Some(_) => match parse_type(&mut chars) {
+						Some(t) => args.push(t),
+						None => return None,
+					},
+					None => return None,
+				}
+			}
+
+			let ret = match parse_type(&mut chars) {
+				Some(t) => t,
+				None => return None,
+			};
+			if chars.next().is_some() {
+				return None;
+			}
+
+			Some(TypeSignature { args: args, ret: ret })
+		}
+	}
+}
+
+/// A JNI argument or return value, used to marshal calls through
+/// `call_method`/`call_static_method`/`new_object` and friends.
+#[derive(Debug, Clone, Copy)]
+pub enum JValue {
+	Boolean(jboolean),
+	Byte(jbyte),
+	Char(jchar),
+	Short(jshort),
+	Int(jint),
+	Long(jlong),
+	Float(jfloat),
+	Double(jdouble),
+	/// A local reference to an object, or `0 as jobject` for `null`.
+	Object(jobject),
+	Void,
+}
+
+impl JValue {
+	/// Wraps the object's underlying `jobject` pointer as an argument value.
+	pub fn from_obj<'a, T: 'a + JObject<'a>>(obj: &T) -> JValue {
+		JValue::Object(obj.get_obj())
+	}
+
+	fn to_raw(&self) -> jvalue {
+		match *self {
+			JValue::Boolean(v) => jvalue { z: v },
+			JValue::Byte(v) => jvalue { b: v },
+			JValue::Char(v) => jvalue { c: v },
+			JValue::Short(v) => jvalue { s: v },
+			JValue::Int(v) => jvalue { i: v },
+			JValue::Long(v) => jvalue { j: v },
+			JValue::Float(v) => jvalue { f: v },
+			JValue::Double(v) => jvalue { d: v },
+			JValue::Object(v) => jvalue { l: v },
+			JValue::Void => jvalue { j: 0 },
+		}
+	}
+}
+
+impl<'a> JavaEnv<'a> {
+	fn get_method_id(&self, class: jclass, name: &str, descriptor: &str, _cap: &Capability) -> jmethodID {
+		self.resolve_id(&self.method_ids, class, name, descriptor, |ptr, class, jname, jdesc| unsafe {
+			((**ptr).GetMethodID)(ptr, class, jname.as_ptr(), jdesc.as_ptr())
+		})
+	}
+
+	fn get_static_method_id(&self, class: jclass, name: &str, descriptor: &str, _cap: &Capability) -> jmethodID {
+		self.resolve_id(&self.static_method_ids, class, name, descriptor, |ptr, class, jname, jdesc| unsafe {
+			((**ptr).GetStaticMethodID)(ptr, class, jname.as_ptr(), jdesc.as_ptr())
+		})
+	}
+
+	fn get_field_id(&self, class: jclass, name: &str, descriptor: &str, _cap: &Capability) -> jfieldID {
+		self.resolve_id(&self.field_ids, class, name, descriptor, |ptr, class, jname, jdesc| unsafe {
+			((**ptr).GetFieldID)(ptr, class, jname.as_ptr(), jdesc.as_ptr())
+		})
+	}
+
+	fn get_static_field_id(&self, class: jclass, name: &str, descriptor: &str, _cap: &Capability) -> jfieldID {
+		self.resolve_id(&self.static_field_ids, class, name, descriptor, |ptr, class, jname, jdesc| unsafe {
+			((**ptr).GetStaticFieldID)(ptr, class, jname.as_ptr(), jdesc.as_ptr())
+		})
+	}
+
+	fn resolve_id<ID, F>(&self, cache: &RefCell<HashMap<(usize, string::String, string::String), ID>>, class: jclass, name: &str, descriptor: &str, resolve: F) -> ID
+		where ID: Copy + PartialEq, F: FnOnce(*mut JNIEnvImpl, jclass, &JavaChars, &JavaChars) -> ID {
+		let key = (class as usize, name.to_string(), descriptor.to_string());
+		if let Some(id) = self.method_cache_get(cache, &key) {
+			return id;
+		}
+		let jname = JavaChars::new(name);
+		let jdesc = JavaChars::new(descriptor);
+		let id = resolve(self.ptr, class, &jname, &jdesc);
+		// A null ID means the lookup failed and raised an exception; don't
+		// cache it, so a later lookup raises (and reports) it again.
+		if id != unsafe { mem::zeroed() } {
+			cache.borrow_mut().insert(key, id);
+		}
+		id
+	}
+
+	fn method_cache_get<ID: Copy>(&self, cache: &RefCell<HashMap<(usize, string::String, string::String), ID>>, key: &(usize, string::String, string::String)) -> Option<ID> {
+		cache.borrow().get(key).cloned()
+	}
+
+	unsafe fn call_by_ret(&self, obj: jobject, mid: jmethodID, ret: &signature::JavaType, args: *const jvalue) -> JValue {
+		use self::signature::{JavaType, Primitive};
+		match *ret {
+			JavaType::Primitive(Primitive::Void) => { ((**self.ptr).CallVoidMethodA)(self.ptr, obj, mid, args); JValue::Void },
+			JavaType::Primitive(Primitive::Boolean) => JValue::Boolean(((**self.ptr).CallBooleanMethodA)(self.ptr, obj, mid, args)),
+			JavaType::Primitive(Primitive::Byte) => JValue::Byte(((**self.ptr).CallByteMethodA)(self.ptr, obj, mid, args)),
+			JavaType::Primitive(Primitive::Char) => JValue::Char(((**self.ptr).CallCharMethodA)(self.ptr, obj, mid, args)),
+			JavaType::Primitive(Primitive::Short) => JValue::Short(((**self.ptr).CallShortMethodA)(self.ptr, obj, mid, args)),
+			JavaType::Primitive(Primitive::Int) => JValue::Int(((**self.ptr).CallIntMethodA)(self.ptr, obj, mid, args)),
+			JavaType::Primitive(Primitive::Long) => JValue::Long(((**self.ptr).CallLongMethodA)(self.ptr, obj, mid, args)),
+			JavaType::Primitive(Primitive::Float) => JValue::Float(((**self.ptr).CallFloatMethodA)(self.ptr, obj, mid, args)),
+			JavaType::Primitive(Primitive::Double) => JValue::Double(((**self.ptr).CallDoubleMethodA)(self.ptr, obj, mid, args)),
+			JavaType::Object(_) | JavaType::Array(_) => JValue::Object(((**self.ptr).CallObjectMethodA)(self.ptr, obj, mid, args)),
+		}
+	}
+
+	unsafe fn call_static_by_ret(&self, class: jclass, mid: jmethodID, ret: &signature::JavaType, args: *const jvalue) -> JValue {
+		use self::signature::{JavaType, Primitive};
+		match *ret {
+			JavaType::Primitive(Primitive::Void) => { ((**self.ptr).CallStaticVoidMethodA)(self.ptr, class, mid, args); JValue::Void },
+			JavaType::Primitive(Primitive::Boolean) => JValue::Boolean(((**self.ptr).CallStaticBooleanMethodA)(self.ptr, class, mid, args)),
+			JavaType::Primitive(Primitive::Byte) => JValue::Byte(((**self.ptr).CallStaticByteMethodA)(self.ptr, class, mid, args)),
+			JavaType::Primitive(Primitive::Char) => JValue::Char(((**self.ptr).CallStaticCharMethodA)(self.ptr, class, mid, args)),
+			JavaType::Primitive(Primitive::Short) => JValue::Short(((**self.ptr).CallStaticShortMethodA)(self.ptr, class, mid, args)),
+			JavaType::Primitive(Primitive::Int) => JValue::Int(((**self.ptr).CallStaticIntMethodA)(self.ptr, class, mid, args)),
+			JavaType::Primitive(Primitive::Long) => JValue::Long(((**self.ptr).CallStaticLongMethodA)(self.ptr, class, mid, args)),
+			JavaType::Primitive(Primitive::Float) => JValue::Float(((**self.ptr).CallStaticFloatMethodA)(self.ptr, class, mid, args)),
+			JavaType::Primitive(Primitive::Double) => JValue::Double(((**self.ptr).CallStaticDoubleMethodA)(self.ptr, class, mid, args)),
+			JavaType::Object(_) | JavaType::Array(_) => JValue::Object(((**self.ptr).CallStaticObjectMethodA)(self.ptr, class, mid, args)),
+		}
+	}
+
+	unsafe fn call_nonvirtual_by_ret(&self, obj: jobject, class: jclass, mid: jmethodID, ret: &signature::JavaType, args: *const jvalue) -> JValue {
+		use self::signature::{JavaType, Primitive};
+		match *ret {
+			JavaType::Primitive(Primitive::Void) => { ((**self.ptr).CallNonvirtualVoidMethodA)(self.ptr, obj, class, mid, args); JValue::Void },
+			JavaType::Primitive(Primitive::Boolean) => JValue::Boolean(((**self.ptr).CallNonvirtualBooleanMethodA)(self.ptr, obj, class, mid, args)),
+			JavaType::Primitive(Primitive::Byte) => JValue::Byte(((**self.ptr).CallNonvirtualByteMethodA)(self.ptr, obj, class, mid, args)),
+			JavaType::Primitive(Primitive::Char) => JValue::Char(((**self.ptr).CallNonvirtualCharMethodA)(self.ptr, obj, class, mid, args)),
+			JavaType::Primitive(Primitive::Short) => JValue::Short(((**self.ptr).CallNonvirtualShortMethodA)(self.ptr, obj, class, mid, args)),
+			JavaType::Primitive(Primitive::Int) => JValue::Int(((**self.ptr).CallNonvirtualIntMethodA)(self.ptr, obj, class, mid, args)),
+			JavaType::Primitive(Primitive::Long) => JValue::Long(((**self.ptr).CallNonvirtualLongMethodA)(self.ptr, obj, class, mid, args)),
+			JavaType::Primitive(Primitive::Float) => JValue::Float(((**self.ptr).CallNonvirtualFloatMethodA)(self.ptr, obj, class, mid, args)),
+			JavaType::Primitive(Primitive::Double) => JValue::Double(((**self.ptr).CallNonvirtualDoubleMethodA)(self.ptr, obj, class, mid, args)),
+			JavaType::Object(_) | JavaType::Array(_) => JValue::Object(((**self.ptr).CallNonvirtualObjectMethodA)(self.ptr, obj, class, mid, args)),
+		}
+	}
+
+	/// Calls an instance method by name and JNI descriptor (e.g.
+	/// `"(I)Ljava/lang/String;"`), dispatching to the right typed
+	/// `Call*MethodA` based on the descriptor's return type.
+	pub fn call_method<T: 'a + JObject<'a>>(&self, obj: &T, name: &str, descriptor: &str, args: &[JValue], cap: Capability) -> JniResult<JValue> {
+		let sig = signature::TypeSignature::parse(descriptor).expect("invalid JNI method descriptor");
+		let class = obj.get_class(&cap);
+		let mid = self.get_method_id(class.ptr, name, descriptor, &cap);
+		if mid == 0 as jmethodID {
+			return Err(Exception::new());
+		}
+		let jargs: Vec<jvalue> = args.iter().map(JValue::to_raw).collect();
+		let (result, _) = unsafe {
+			(self.call_by_ret(obj.get_obj(), mid, &sig.ret, jargs.as_ptr()), cap)
+		};
+		// here `cap` is taken, we can't call any Jni methods
+		match self.exception_check() {
+			Ok(cap) => Ok((result, cap)),
+			Err(exn) => Err(exn),
+		}
+	}
+
+	/// Calls a static method by name and JNI descriptor.
+	pub fn call_static_method(&self, class: &JavaClass, name: &str, descriptor: &str, args: &[JValue], cap: Capability) -> JniResult<JValue> {
+		let sig = signature::TypeSignature::parse(descriptor).expect("invalid JNI method descriptor");
+		let mid = self.get_static_method_id(class.ptr, name, descriptor, &cap);
+		if mid == 0 as jmethodID {
+			return Err(Exception::new());
+		}
+		let jargs: Vec<jvalue> = args.iter().map(JValue::to_raw).collect();
+		let (result, _) = unsafe {
+			(self.call_static_by_ret(class.ptr, mid, &sig.ret, jargs.as_ptr()), cap)
+		};
+		// here `cap` is taken, we can't call any Jni methods
+		match self.exception_check() {
+			Ok(cap) => Ok((result, cap)),
+			Err(exn) => Err(exn),
+		}
+	}
+
+	/// Calls a method non-virtually (as if statically bound to `class`),
+	/// bypassing dynamic dispatch -- the JNI equivalent of `Class.super.method()`.
+	pub fn call_nonvirtual_method<T: 'a + JObject<'a>>(&self, obj: &T, class: &JavaClass, name: &str, descriptor: &str, args: &[JValue], cap: Capability) -> JniResult<JValue> {
+		let sig = signature::TypeSignature::parse(descriptor).expect("invalid JNI method descriptor");
+		let mid = self.get_method_id(class.ptr, name, descriptor, &cap);
+		if mid == 0 as jmethodID {
+			return Err(Exception::new());
+		}
+		let jargs: Vec<jvalue> = args.iter().map(JValue::to_raw).collect();
+		let (result, _) = unsafe {
+			(self.call_nonvirtual_by_ret(obj.get_obj(), class.ptr, mid, &sig.ret, jargs.as_ptr()), cap)
+		};
+		// here `cap` is taken, we can't call any Jni methods
+		match self.exception_check() {
+			Ok(cap) => Ok((result, cap)),
+			Err(exn) => Err(exn),
+		}
+	}
+
+	/// Reads an instance field by name and JNI type descriptor (e.g. `"I"`).
+	/// Resolving the field ID can raise `NoSuchFieldError` if `descriptor`
+	/// doesn't match any field on `obj`'s class.
+	pub fn get_field<T: 'a + JObject<'a>>(&self, obj: &T, name: &str, descriptor: &str, cap: Capability) -> JniResult<JValue> {
+		use self::signature::{JavaType, Primitive};
+		let class = obj.get_class(&cap);
+		let typ = signature::JavaType::parse(descriptor).expect("invalid JNI field descriptor");
+		let fid = self.get_field_id(class.ptr, name, descriptor, &cap);
+		if fid == 0 as jfieldID {
+			return Err(Exception::new());
+		}
+		let result = unsafe {
+			match typ {
+				JavaType::Primitive(Primitive::Boolean) => JValue::Boolean(((**self.ptr).GetBooleanField)(self.ptr, obj.get_obj(), fid)),
+				JavaType::Primitive(Primitive::Byte) => JValue::Byte(((**self.ptr).GetByteField)(self.ptr, obj.get_obj(), fid)),
+				JavaType::Primitive(Primitive::Char) => JValue::Char(((**self.ptr).GetCharField)(self.ptr, obj.get_obj(), fid)),
+				JavaType::Primitive(Primitive::Short) => JValue::Short(((**self.ptr).GetShortField)(self.ptr, obj.get_obj(), fid)),
+				JavaType::Primitive(Primitive::Int) => JValue::Int(((**self.ptr).GetIntField)(self.ptr, obj.get_obj(), fid)),
+				JavaType::Primitive(Primitive::Long) => JValue::Long(((**self.ptr).GetLongField)(self.ptr, obj.get_obj(), fid)),
+				JavaType::Primitive(Primitive::Float) => JValue::Float(((**self.ptr).GetFloatField)(self.ptr, obj.get_obj(), fid)),
+				JavaType::Primitive(Primitive::Double) => JValue::Double(((**self.ptr).GetDoubleField)(self.ptr, obj.get_obj(), fid)),
+				JavaType::Primitive(Primitive::Void) => panic!("field cannot have type void"),
+				JavaType::Object(_) | JavaType::Array(_) => JValue::Object(((**self.ptr).GetObjectField)(self.ptr, obj.get_obj(), fid)),
+			}
+		};
+		Ok((result, cap))
+	}
+
+	/// Writes an instance field by name and JNI type descriptor.
+	/// Resolving the field ID can raise `NoSuchFieldError`.
+	pub fn set_field<T: 'a + JObject<'a>>(&self, obj: &T, name: &str, descriptor: &str, value: JValue, cap: Capability) -> JniResult<()> {
+		let class = obj.get_class(&cap);
+		let fid = self.get_field_id(class.ptr, name, descriptor, &cap);
+		if fid == 0 as jfieldID {
+			return Err(Exception::new());
+		}
+		unsafe {
+			match value {
+				JValue::Boolean(v) => ((**self.ptr).SetBooleanField)(self.ptr, obj.get_obj(), fid, v),
+				JValue::Byte(v) => ((**self.ptr).SetByteField)(self.ptr, obj.get_obj(), fid, v),
+				JValue::Char(v) => ((**self.ptr).SetCharField)(self.ptr, obj.get_obj(), fid, v),
+				JValue::Short(v) => ((**self.ptr).SetShortField)(self.ptr, obj.get_obj(), fid, v),
+				JValue::Int(v) => ((**self.ptr).SetIntField)(self.ptr, obj.get_obj(), fid, v),
+				JValue::Long(v) => ((**self.ptr).SetLongField)(self.ptr, obj.get_obj(), fid, v),
+				JValue::Float(v) => ((**self.ptr).SetFloatField)(self.ptr, obj.get_obj(), fid, v),
+				JValue::Double(v) => ((**self.ptr).SetDoubleField)(self.ptr, obj.get_obj(), fid, v),
+				JValue::Object(v) => ((**self.ptr).SetObjectField)(self.ptr, obj.get_obj(), fid, v),
+				JValue::Void => panic!("field cannot have type void"),
+			}
+		}
+		Ok(((), cap))
+	}
+
+	/// Reads a static field by name and JNI type descriptor.
+	/// Resolving the field ID can raise `NoSuchFieldError`.
+	pub fn get_static_field(&self, class: &JavaClass, name: &str, descriptor: &str, cap: Capability) -> JniResult<JValue> {
+		use self::signature::{JavaType, Primitive};
+		let typ = signature::JavaType::parse(descriptor).expect("invalid JNI field descriptor");
+		let fid = self.get_static_field_id(class.ptr, name, descriptor, &cap);
+		if fid == 0 as jfieldID {
+			return Err(Exception::new());
+		}
+		let result = unsafe {
+			match typ {
+				JavaType::Primitive(Primitive::Boolean) => JValue::Boolean(((**self.ptr).GetStaticBooleanField)(self.ptr, class.ptr, fid)),
+				JavaType::Primitive(Primitive::Byte) => JValue::Byte(((**self.ptr).GetStaticByteField)(self.ptr, class.ptr, fid)),
+				JavaType::Primitive(Primitive::Char) => JValue::Char(((**self.ptr).GetStaticCharField)(self.ptr, class.ptr, fid)),
+				JavaType::Primitive(Primitive::Short) => JValue::Short(((**self.ptr).GetStaticShortField)(self.ptr, class.ptr, fid)),
+				JavaType::Primitive(Primitive::Int) => JValue::Int(((**self.ptr).GetStaticIntField)(self.ptr, class.ptr, fid)),
+				JavaType::Primitive(Primitive::Long) => JValue::Long(((**self.ptr).GetStaticLongField)(self.ptr, class.ptr, fid)),
+				JavaType::Primitive(Primitive::Float) => JValue::Float(((**self.ptr).GetStaticFloatField)(self.ptr, class.ptr, fid)),
+				JavaType::Primitive(Primitive::Double) => JValue::Double(((**self.ptr).GetStaticDoubleField)(self.ptr, class.ptr, fid)),
+				JavaType::Primitive(Primitive::Void) => panic!("field cannot have type void"),
+				JavaType::Object(_) | JavaType::Array(_) => JValue::Object(((**self.ptr).GetStaticObjectField)(self.ptr, class.ptr, fid)),
+			}
+		};
+		Ok((result, cap))
+	}
+
+	/// Writes a static field by name and JNI type descriptor.
+	/// Resolving the field ID can raise `NoSuchFieldError`.
+	pub fn set_static_field(&self, class: &JavaClass, name: &str, descriptor: &str, value: JValue, cap: Capability) -> JniResult<()> {
+		let fid = self.get_static_field_id(class.ptr, name, descriptor, &cap);
+		if fid == 0 as jfieldID {
+			return Err(Exception::new());
+		}
+		unsafe {
+			match value {
+				JValue::Boolean(v) => ((**self.ptr).SetStaticBooleanField)(self.ptr, class.ptr, fid, v),
+				JValue::Byte(v) => ((**self.ptr).SetStaticByteField)(self.ptr, class.ptr, fid, v),
+				JValue::Char(v) => ((**self.ptr).SetStaticCharField)(self.ptr, class.ptr, fid, v),
+				JValue::Short(v) => ((**self.ptr).SetStaticShortField)(self.ptr, class.ptr, fid, v),
+				JValue::Int(v) => ((**self.ptr).SetStaticIntField)(self.ptr, class.ptr, fid, v),
+				JValue::Long(v) => ((**self.ptr).SetStaticLongField)(self.ptr, class.ptr, fid, v),
+				JValue::Float(v) => ((**self.ptr).SetStaticFloatField)(self.ptr, class.ptr, fid, v),
+				JValue::Double(v) => ((**self.ptr).SetStaticDoubleField)(self.ptr, class.ptr, fid, v),
+				JValue::Object(v) => ((**self.ptr).SetStaticObjectField)(self.ptr, class.ptr, fid, v),
+				JValue::Void => panic!("field cannot have type void"),
+			}
+		}
+		Ok(((), cap))
+	}
+
+	/// Constructs a new object by calling the constructor identified by
+	/// `descriptor` (e.g. `"(I)V"`) with `args`.
+	pub fn new_object(&self, class: &JavaClass, descriptor: &str, args: &[JValue], cap: Capability) -> JniResult<JavaObject> {
+		let mid = self.get_method_id(class.ptr, "<init>", descriptor, &cap);
+		if mid == 0 as jmethodID {
+			return Err(Exception::new());
+		}
+		let jargs: Vec<jvalue> = args.iter().map(JValue::to_raw).collect();
+		let (obj, _) = unsafe {
+			(((**self.ptr).NewObjectA)(self.ptr, class.ptr, mid, jargs.as_ptr()), cap)
+		};
+		// here `cap` is taken, we can't call any Jni methods
+		if obj == 0 as jobject {
+			Err(Exception::new())
+		} else {
+			Ok((unsafe { JObject::from_unsafe(self, obj) }, Capability::new()))
+		}
+	}
+}
+
+/// A safe wrapper around a `java/util/List` (e.g. `ArrayList`), caching
+/// the `size`/`get`/`add`/`remove` method IDs on construction so that
+/// iterating doesn't pay for a `GetMethodID` lookup per element.
+pub struct JavaList<'a, T: 'a> {
+	obj: JavaObject<'a>,
+	mid_size: jmethodID,
+	mid_get: jmethodID,
+	mid_add: jmethodID,
+	mid_remove: jmethodID,
+	phantom: PhantomData<T>,
+}
+
+impl<'a, T: 'a + JObject<'a>> JavaList<'a, T> {
+	/// Wraps `obj` (expected to implement `java/util/List`), resolving
+	/// and caching its method IDs. Fails if `obj`'s class doesn't
+	/// implement one of the expected methods.
+	pub fn new(obj: JavaObject<'a>, cap: Capability) -> JniResult<JavaList<'a, T>> {
+		let env = obj.get_env();
+		let class = obj.get_class(&cap);
+		let mid_size = env.get_method_id(class.ptr, "size", "()I", &cap);
+		if mid_size == 0 as jmethodID {
+			return Err(Exception::new());
+		}
+		let mid_get = env.get_method_id(class.ptr, "get", "(I)Ljava/lang/Object;", &cap);
+		if mid_get == 0 as jmethodID {
+			return Err(Exception::new());
+		}
+		let mid_add = env.get_method_id(class.ptr, "add", "(Ljava/lang/Object;)Z", &cap);
+		if mid_add == 0 as jmethodID {
+			return Err(Exception::new());
+		}
+		let mid_remove = env.get_method_id(class.ptr, "remove", "(I)Ljava/lang/Object;", &cap);
+		if mid_remove == 0 as jmethodID {
+			return Err(Exception::new());
+		}
+		Ok((JavaList {
+			obj: obj,
+			mid_size: mid_size,
+			mid_get: mid_get,
+			mid_add: mid_add,
+			mid_remove: mid_remove,
+			phantom: PhantomData,
+		}, cap))
+	}
+
+	/// The list's current length, via the cached `size` method ID.
+	pub fn size(&self, cap: Capability) -> JniResult<jsize> {
+		use self::signature::{JavaType, Primitive};
+		let env = self.obj.get_env();
+		let no_args: Vec<jvalue> = Vec::new();
+		let (result, _) = unsafe {
+			(env.call_by_ret(self.obj.get_obj(), self.mid_size, &JavaType::Primitive(Primitive::Int), no_args.as_ptr()), cap)
+		};
+		// here `cap` is taken, we can't call any Jni methods
+		match env.exception_check() {
+			Ok(cap) => match result {
+				JValue::Int(n) => Ok((n, cap)),
+				_ => unreachable!(),
+			},
+			Err(exn) => Err(exn),
+		}
+	}
+
+	/// Reads the element at `index`, or `None` if the list holds a
+	/// `null` there. Raises `IndexOutOfBoundsException` if `index` is
+	/// out of range.
+	pub fn get(&'a self, index: jsize, cap: Capability) -> JniResult<Option<T>> {
+		use self::signature::JavaType;
+		let env = self.obj.get_env();
+		let args = [JValue::Int(index).to_raw()];
+		let (result, _) = unsafe {
+			(env.call_by_ret(self.obj.get_obj(), self.mid_get, &JavaType::Object("java/lang/Object".to_string()), args.as_ptr()), cap)
+		};
+		// here `cap` is taken, we can't call any Jni methods
+		match env.exception_check() {
+			Ok(cap) => match result {
+				JValue::Object(obj) => Ok((JObject::from(env, obj), cap)),
+				_ => unreachable!(),
+			},
+			Err(exn) => Err(exn),
+		}
+	}
+
+	/// Appends `value`, returning whether the list changed as a result
+	/// (per `Collection.add`'s contract).
+	pub fn add(&self, value: &T, cap: Capability) -> JniResult<bool> {
+		use self::signature::{JavaType, Primitive};
+		let env = self.obj.get_env();
+		let args = [JValue::from_obj(value).to_raw()];
+		let (result, _) = unsafe {
+			(env.call_by_ret(self.obj.get_obj(), self.mid_add, &JavaType::Primitive(Primitive::Boolean), args.as_ptr()), cap)
+		};
+		// here `cap` is taken, we can't call any Jni methods
+		match env.exception_check() {
+			Ok(cap) => match result {
+				JValue::Boolean(v) => Ok((v != 0, cap)),
+				_ => unreachable!(),
+			},
+			Err(exn) => Err(exn),
+		}
+	}
+
+	/// Removes and returns the element at `index` (or `None` if it was
+	/// `null`). Raises `IndexOutOfBoundsException` if `index` is out of
+	/// range.
+	pub fn remove(&'a self, index: jsize, cap: Capability) -> JniResult<Option<T>> {
+		use self::signature::JavaType;
+		let env = self.obj.get_env();
+		let args = [JValue::Int(index).to_raw()];
+		let (result, _) = unsafe {
+			(env.call_by_ret(self.obj.get_obj(), self.mid_remove, &JavaType::Object("java/lang/Object".to_string()), args.as_ptr()), cap)
+		};
+		// here `cap` is taken, we can't call any Jni methods
+		match env.exception_check() {
+			Ok(cap) => match result {
+				JValue::Object(obj) => Ok((JObject::from(env, obj), cap)),
+				_ => unreachable!(),
+			},
+			Err(exn) => Err(exn),
+		}
+	}
+
+	/// Iterates the list's elements via repeated `get` calls. Each item
+	/// is itself a `JniResult<Option<T>>`: `Some(Err(_))` surfaces a
+	/// pending exception raised mid-iteration (after which the iterator
+	/// is exhausted), and `Some(Ok((None, _)))` is a `null` element --
+	/// neither case is conflated with `None`, which means the list is
+	/// exhausted.
+	pub fn iter(&'a self, cap: Capability) -> JniResult<JavaListIter<'a, T>> {
+		match self.size(cap) {
+			Ok((len, cap)) => Ok((JavaListIter { list: self, index: 0, len: len, cap: RefCell::new(Some(cap)) }, Capability::new())),
+			Err(exn) => Err(exn),
+		}
+	}
+}
+
+/// An iterator over a `JavaList`'s elements, returned by `JavaList::iter`.
+pub struct JavaListIter<'a, T: 'a> {
+	list: &'a JavaList<'a, T>,
+	index: jsize,
+	len: jsize,
+	cap: RefCell<Option<Capability>>,
+}
+
+impl<'a, T: 'a + JObject<'a>> Iterator for JavaListIter<'a, T> {
+	type Item = JniResult<Option<T>>;
+
+	/// Returns `None` once the list is exhausted. Before that, every
+	/// element is surfaced as `Some(_)`, including a `null` element
+	/// (`Some(Ok((None, _)))`) and a pending exception raised by the
+	/// underlying `get` call (`Some(Err(_))`, after which the iterator
+	/// is exhausted) -- neither is silently swallowed into `None`.
+	fn next(&mut self) -> Option<JniResult<Option<T>>> {
+		if self.index >= self.len {
+			return None;
+		}
+		let cap = match self.cap.borrow_mut().take() {
+			Some(cap) => cap,
+			None => return None,
+		};
+		match self.list.get(self.index, cap) {
+			Ok((elem, cap)) => {
+				self.index += 1;
+				*self.cap.borrow_mut() = Some(Capability::new());
+				Some(Ok((elem, cap)))
+			}
+			Err(exn) => {
+				self.index = self.len;
+				Some(Err(exn))
+			}
+		}
+	}
+}
+
+/// A `java.nio.ByteBuffer` created over Rust-owned memory via
+/// `NewDirectByteBuffer`, letting native code and Java share a buffer
+/// without copying through a byte array.
+#[derive(Debug)]
+pub struct JavaByteBuffer<'a> {
+	env: &'a JavaEnv<'a>,
+	ptr: jobject,
+	rtype: RefType,
+}
+
+impl_jobject!(JavaByteBuffer, jobject);
+
+impl<'a> JavaByteBuffer<'a> {
+	/// Wraps `buf` as a direct `ByteBuffer` backed by the same memory.
+	/// `buf` must outlive the returned `JavaByteBuffer` and any Java code
+	/// that might still hold a reference to it. Tying `buf` to `'b` makes
+	/// the borrow checker enforce the former; nothing here enforces the
+	/// latter.
+	pub fn from_slice<'b>(env: &'b JavaEnv<'b>, buf: &'b mut [u8], cap: Capability) -> JniResult<JavaByteBuffer<'b>> {
+		let (obj, _) = unsafe {
+			(((**env.ptr).NewDirectByteBuffer)(env.ptr, buf.as_mut_ptr() as *mut ::libc::c_void, buf.len() as jlong), cap)
+		};
+		// here `cap` is taken, we can't call any Jni methods
+		if obj == 0 as jobject {
+			Err(Exception::new())
+		} else {
+			Ok((unsafe { JObject::from_unsafe(env, obj) }, Capability::new()))
+		}
+	}
+
+	/// Reads back the address and capacity `GetDirectBufferAddress`/
+	/// `GetDirectBufferCapacity` report for this buffer.
+	fn direct_buffer_info(&self) -> (*mut ::libc::c_void, jlong) {
+		unsafe {
+			let addr = ((**self.env.ptr).GetDirectBufferAddress)(self.env.ptr, self.ptr);
+			let cap = ((**self.env.ptr).GetDirectBufferCapacity)(self.env.ptr, self.ptr);
+			(addr, cap)
+		}
+	}
+
+	/// Reconstructs the backing memory as a `&[u8]`. Returns `None` if
+	/// this buffer isn't direct (`GetDirectBufferAddress` returned
+	/// `null`), e.g. because it came from Java as a non-direct
+	/// `ByteBuffer`.
+	pub fn as_slice(&self) -> Option<&[u8]> {
+		let (addr, len) = self.direct_buffer_info();
+		if addr.is_null() {
+			None
+		} else {
+			Some(unsafe { ::std::slice::from_raw_parts(addr as *const u8, len as usize) })
+		}
+	}
+
+	/// Reconstructs the backing memory as a `&mut [u8]`. Returns `None`
+	/// if this buffer isn't direct. Takes `&mut self` so the borrow
+	/// checker rules out holding this alongside another `as_slice`/
+	/// `as_mut_slice` borrow of the same memory.
+	pub fn as_mut_slice(&mut self) -> Option<&mut [u8]> {
+		let (addr, len) = self.direct_buffer_info();
+		if addr.is_null() {
+			None
+		} else {
+			Some(unsafe { ::std::slice::from_raw_parts_mut(addr as *mut u8, len as usize) })
+		}
+	}
+}
+
+/// Converts a Rust value into its JNI representation, to marshal
+/// arguments for `call_method`/`new_object` and friends without building
+/// raw `jobject`s by hand.
+///
+/// (`Target` was originally named `Raw`; renamed for clarity once this
+/// trait pair and `FromJava` had already settled into their current
+/// shape, no behavior changed.)
+pub trait IntoJava<'a> {
+	type Target;
+	fn into_java(self, env: &'a JavaEnv<'a>, cap: Capability) -> JniResult<Self::Target>;
+}
+
+/// Converts a JNI value back into an idiomatic Rust value.
+pub trait FromJava<'a>: Sized {
+	type Target;
+	fn from_java(env: &'a JavaEnv<'a>, raw: Self::Target, cap: Capability) -> JniResult<Self>;
+}
+
+macro_rules! impl_java_primitive_conversion(
+	($rust_ty:ty) => (
+		impl<'a> IntoJava<'a> for $rust_ty {
+			type Target = $rust_ty;
+			fn into_java(self, _env: &'a JavaEnv<'a>, cap: Capability) -> JniResult<$rust_ty> {
+				Ok((self, cap))
+			}
+		}
+
+		impl<'a> FromJava<'a> for $rust_ty {
+			type Target = $rust_ty;
+			fn from_java(_env: &'a JavaEnv<'a>, raw: $rust_ty, cap: Capability) -> JniResult<$rust_ty> {
+				Ok((raw, cap))
+			}
+		}
+	);
+);
+
+impl_java_primitive_conversion!(jbyte);
+impl_java_primitive_conversion!(jchar);
+impl_java_primitive_conversion!(jshort);
+impl_java_primitive_conversion!(jint);
+impl_java_primitive_conversion!(jlong);
+impl_java_primitive_conversion!(jfloat);
+impl_java_primitive_conversion!(jdouble);
+
+impl<'a> IntoJava<'a> for bool {
+	type Target = jboolean;
+	fn into_java(self, _env: &'a JavaEnv<'a>, cap: Capability) -> JniResult<jboolean> {
+		Ok((if self { JNI_TRUE } else { JNI_FALSE }, cap))
+	}
+}
+
+impl<'a> FromJava<'a> for bool {
+	type Target = jboolean;
+	fn from_java(_env: &'a JavaEnv<'a>, raw: jboolean, cap: Capability) -> JniResult<bool> {
+		Ok((raw == JNI_TRUE, cap))
+	}
+}
+
+impl<'a, 'b> IntoJava<'a> for &'b str {
+	type Target = jobject;
+	fn into_java(self, env: &'a JavaEnv<'a>, cap: Capability) -> JniResult<jobject> {
+		match JavaString::new(env, self, cap) {
+			Ok((s, cap)) => Ok((s.into_raw(), cap)),
+			Err(exn) => Err(exn),
+		}
+	}
+}
+
+impl<'a> IntoJava<'a> for string::String {
+	type Target = jobject;
+	fn into_java(self, env: &'a JavaEnv<'a>, cap: Capability) -> JniResult<jobject> {
+		(&self[..]).into_java(env, cap)
+	}
+}
+
+impl<'a> FromJava<'a> for string::String {
+	type Target = jobject;
+	fn from_java(env: &'a JavaEnv<'a>, raw: jobject, cap: Capability) -> JniResult<string::String> {
+		let s: JavaString = unsafe { JObject::from_unsafe(env, raw) };
+		let string = s.to_str().expect("invalid modified UTF-8 string");
+		Ok((string, cap))
+	}
+}
+
+/// How a `Vec<Self>` marshals into a Java array: object types allocate a
+/// `[L<class>;` and fill it with a per-element `IntoJava` loop; primitives
+/// (see `impl_primitive_array_element!` below) allocate the matching
+/// primitive array type and fill it with a single bulk `SetXArrayRegion`
+/// call instead of boxing each element.
+pub trait JavaArrayElement<'a>: Sized {
+	fn vec_into_array(values: Vec<Self>, env: &'a JavaEnv<'a>, cap: Capability) -> JniResult<jobject>;
+}
+
+/// The `FromJava` counterpart of `JavaArrayElement`, kept as a separate
+/// trait since some element types (e.g. `&str`) can convert into a Java
+/// array but can't be reconstructed from one.
+pub trait FromJavaArrayElement<'a>: Sized {
+	fn vec_from_array(env: &'a JavaEnv<'a>, raw: jobject, cap: Capability) -> JniResult<Vec<Self>>;
+}
+
+/// Allocates a `[L<class_name>;` array and fills it by converting each
+/// element through `IntoJava`.
+fn object_vec_into_array<'a, E: IntoJava<'a, Target = jobject>>(class_name: &str, values: Vec<E>, env: &'a JavaEnv<'a>, cap: Capability) -> JniResult<jobject> {
+	let (class, mut cap) = env.find_class(class_name, cap).expect("element class not found");
+	let (array, new_cap) = match env.new_object_array::<JavaObject>(values.len() as jsize, &class, None, cap) {
+		Ok(r) => r,
+		Err(exn) => return Err(exn),
+	};
+	cap = new_cap;
+
+	for (i, elem) in values.into_iter().enumerate() {
+		let (raw, new_cap) = match elem.into_java(env, cap) {
+			Ok(r) => r,
+			Err(exn) => return Err(exn),
+		};
+		cap = new_cap;
+		unsafe {
+			((**env.ptr).SetObjectArrayElement)(env.ptr, array.ptr, i as jsize, raw);
+		}
+	}
+
+	Ok((array.into_raw(), cap))
+}
+
+/// Reads every element of a Java object array, converting each through
+/// `FromJava`.
+fn object_vec_from_array<'a, E: FromJava<'a, Target = jobject>>(env: &'a JavaEnv<'a>, raw: jobject, cap: Capability) -> JniResult<Vec<E>> {
+	let array: JavaArray<JavaObject> = unsafe { JObject::from_unsafe(env, raw) };
+	let len = array.array_length(&cap);
+	let mut cap = cap;
+	let mut result = Vec::with_capacity(len as usize);
+	for i in 0..len {
+		let elem_obj = unsafe { ((**env.ptr).GetObjectArrayElement)(env.ptr, array.ptr, i) };
+		let (elem, new_cap) = match E::from_java(env, elem_obj, cap) {
+			Ok(r) => r,
+			Err(exn) => return Err(exn),
+		};
+		cap = new_cap;
+		result.push(elem);
+	}
+	Ok((result, cap))
+}
+
+impl<'a> JavaArrayElement<'a> for string::String {
+	fn vec_into_array(values: Vec<string::String>, env: &'a JavaEnv<'a>, cap: Capability) -> JniResult<jobject> {
+		object_vec_into_array("java/lang/String", values, env, cap)
+	}
+}
+
+impl<'a> FromJavaArrayElement<'a> for string::String {
+	fn vec_from_array(env: &'a JavaEnv<'a>, raw: jobject, cap: Capability) -> JniResult<Vec<string::String>> {
+		object_vec_from_array(env, raw, cap)
+	}
+}
+
+impl<'a, 'b> JavaArrayElement<'a> for &'b str {
+	fn vec_into_array(values: Vec<&'b str>, env: &'a JavaEnv<'a>, cap: Capability) -> JniResult<jobject> {
+		object_vec_into_array("java/lang/String", values, env, cap)
+	}
+}
+
+/// Converts `Vec<E>` to a Java array the way `E` says to -- a per-element
+/// `IntoJava` loop boxed into an object array, or (for primitives) a bulk
+/// region copy into a primitive array.
+impl<'a, E: JavaArrayElement<'a>> IntoJava<'a> for Vec<E> {
+	type Target = jobject;
+
+	fn into_java(self, env: &'a JavaEnv<'a>, cap: Capability) -> JniResult<jobject> {
+		E::vec_into_array(self, env, cap)
+	}
+}
+
+/// The `FromJava` counterpart of `Vec<E>: IntoJava`.
+impl<'a, E: FromJavaArrayElement<'a>> FromJava<'a> for Vec<E> {
+	type Target = jobject;
+
+	fn from_java(env: &'a JavaEnv<'a>, raw: jobject, cap: Capability) -> JniResult<Vec<E>> {
+		E::vec_from_array(env, raw, cap)
+	}
+}
+
+/// Implements `JavaArrayElement`/`FromJavaArrayElement` for a JNI primitive
+/// type by delegating to `primitive_vec_into_array`/`primitive_array_into_vec`,
+/// so `Vec<jint>` (etc.) goes through the same `IntoJava`/`FromJava`
+/// machinery as `Vec<String>`, just with a bulk region copy instead of a
+/// per-element loop.
+macro_rules! impl_primitive_array_element(
+	($rust_ty:ty) => (
+		impl<'a> JavaArrayElement<'a> for $rust_ty {
+			fn vec_into_array(values: Vec<$rust_ty>, env: &'a JavaEnv<'a>, cap: Capability) -> JniResult<jobject> {
+				match env.primitive_vec_into_array(&values, cap) {
+					Ok((array, cap)) => Ok((array.into_raw(), cap)),
+					Err(exn) => Err(exn),
+				}
+			}
+		}
+
+		impl<'a> FromJavaArrayElement<'a> for $rust_ty {
+			fn vec_from_array(env: &'a JavaEnv<'a>, raw: jobject, cap: Capability) -> JniResult<Vec<$rust_ty>> {
+				let array: JavaArray<$rust_ty> = unsafe { JObject::from_unsafe(env, raw) };
+				let result = env.primitive_array_into_vec(&array, &cap);
+				Ok((result, cap))
+			}
+		}
+	);
+);
+
+impl_primitive_array_element!(jboolean);
+impl_primitive_array_element!(jbyte);
+impl_primitive_array_element!(jchar);
+impl_primitive_array_element!(jshort);
+impl_primitive_array_element!(jint);
+impl_primitive_array_element!(jlong);
+impl_primitive_array_element!(jfloat);
+impl_primitive_array_element!(jdouble);
+
+/// A Java method or field of reference type reads back as `null` far
+/// more often than a primitive one does; mapping that straight to
+/// `Option::None` instead of forcing every caller to check `is_null()`
+/// by hand.
+impl<'a, T: FromJava<'a, Target = jobject>> FromJava<'a> for Option<T> {
+	type Target = jobject;
+
+	fn from_java(env: &'a JavaEnv<'a>, raw: jobject, cap: Capability) -> JniResult<Option<T>> {
+		if raw == 0 as jobject {
+			Ok((None, cap))
+		} else {
+			match T::from_java(env, raw, cap) {
+				Ok((v, cap)) => Ok((Some(v), cap)),
+				Err(exn) => Err(exn),
+			}
+		}
+	}
+}
+
+/// The symmetric counterpart of `FromJava for Option<T>`: `None` marshals
+/// to a `null` `jobject` rather than requiring a sentinel value.
+impl<'a, T: IntoJava<'a, Target = jobject>> IntoJava<'a> for Option<T> {
+	type Target = jobject;
+
+	fn into_java(self, env: &'a JavaEnv<'a>, cap: Capability) -> JniResult<jobject> {
+		match self {
+			Some(v) => v.into_java(env, cap),
+			None => Ok((0 as jobject, cap)),
+		}
+	}
+}
+
+impl<'a> JavaEnv<'a> {
+	/// Like `call_method`, but for methods with an object-typed return:
+	/// converts the result to `Option<T>` via `JObject::from`, so a
+	/// `null` return reads as `None` instead of a pending exception --
+	/// `exception_check` already distinguishes the two before this ever
+	/// sees the result.
+	pub fn call_method_obj<O: 'a + JObject<'a>, T: 'a + JObject<'a>>(&'a self, obj: &O, name: &str, descriptor: &str, args: &[JValue], cap: Capability) -> JniResult<Option<T>> {
+		match self.call_method(obj, name, descriptor, args, cap) {
+			Ok((JValue::Object(ptr), cap)) => Ok((JObject::from(self, ptr), cap)),
+			Ok((_, _)) => panic!("descriptor {} does not return an object type", descriptor),
+			Err(exn) => Err(exn),
+		}
+	}
+}
+
+impl<'a> JavaEnv<'a> {
+	/// Copies `values` into a freshly-allocated Java primitive array via a
+	/// single bulk `Set*ArrayRegion` call. `Vec<T>: IntoJava` (for `T: `
+	/// `JavaPrimitive`) goes through this rather than boxing each element,
+	/// the way `Vec<E>: IntoJava` does for object element types.
+	pub fn primitive_vec_into_array<T: 'a + JavaPrimitive>(&'a self, values: &[T], cap: Capability) -> JniResult<JavaArray<'a, T>> {
+		let (arr, cap) = unsafe {
+			(T::new_array(self.ptr, values.len() as jsize), cap)
+		};
+		// a null `arr` means an exception is pending; otherwise `cap` is
+		// still valid for the `set_region` below
+		if arr == 0 as jarray {
+			return Err(Exception::new());
+		}
+		let array = JavaArray { env: self, ptr: arr, rtype: RefType::Local, phantom: PhantomData };
+		array.set_region(0, values, &cap);
+		match self.exception_check() {
+			Ok(cap) => Ok((array, cap)),
+			Err(exn) => Err(exn),
+		}
+	}
+
+	/// Copies every element of a Java primitive array into a `Vec`.
+	pub fn primitive_array_into_vec<T: 'a + JavaPrimitive>(&'a self, array: &JavaArray<'a, T>, cap: &Capability) -> Vec<T> {
+		let len = array.array_length(cap);
+		let mut result: Vec<T> = Vec::with_capacity(len as usize);
+		unsafe {
+			result.set_len(len as usize);
+		}
+		array.get_region(0, &mut result, cap);
+		result
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
@@ -981,6 +2536,25 @@ mod tests {
 		}
 	}
 
+	#[test]
+	fn test_type_signature_parse() {
+		use super::signature::{TypeSignature, JavaType, Primitive};
+
+		let sig = TypeSignature::parse("(ILjava/lang/String;)[I").unwrap();
+		assert!(sig.args == [JavaType::Primitive(Primitive::Int), JavaType::Object("java/lang/String".to_string())]);
+		assert!(sig.ret == JavaType::Array(Box::new(JavaType::Primitive(Primitive::Int))));
+
+		let sig = TypeSignature::parse("()V").unwrap();
+		assert!(sig.args.is_empty());
+		assert!(sig.ret == JavaType::Primitive(Primitive::Void));
+
+		assert!(TypeSignature::parse("(I").is_none());
+		assert!(TypeSignature::parse("I)V").is_none());
+
+		assert!(JavaType::parse("[[Ljava/lang/Object;").unwrap() ==
+			JavaType::Array(Box::new(JavaType::Array(Box::new(JavaType::Object("java/lang/Object".to_string()))))));
+	}
+
 	#[test]
 	fn test_JavaVMInitArgs() {
 		let args = JavaVMInitArgs::new(
@@ -1018,7 +2592,21 @@ mod tests {
 		let tex = env.exception_check();
 		assert!(tex.is_err());
 		let ex = cls.err().unwrap();
-		let _ = env.exception_clear(ex);
+		let cap = env.exception_clear(ex);
+
+		let (sobj, cap) = JavaString::new(&env, "hi!", cap).unwrap();
+		let (len, cap) = env.call_method(&sobj, "length", "()I", &[], cap).unwrap();
+		assert!(match len { JValue::Int(3) => true, _ => false });
+
+		let (result, cap) = env.try_block(cap, |cap| {
+			env.call_method(&sobj, "length", "()I", &[], cap)
+		}).result().unwrap();
+		assert!(match result { JValue::Int(3) => true, _ => false });
+
+		let (len, _cap) = env.with_local_frame(8, cap, |cap| {
+			env.call_method(&sobj, "length", "()I", &[], cap)
+		}).unwrap();
+		assert!(match len { JValue::Int(3) => true, _ => false });
 	}
 
 	#[test]